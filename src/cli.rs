@@ -0,0 +1,109 @@
+use clap::Parser;
+
+use crate::format::OutputFormat;
+use crate::mem_limit::MemLimit;
+use crate::palette::{Dither, StatsMode};
+use crate::resolution::WidthSpec;
+
+/// Convert a clip into a GIF.
+///
+/// When every flag needed to build a job is supplied, the conversion
+/// runs headless with no prompts; otherwise gif_rs falls back to asking
+/// for whatever is missing.
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+pub struct Args {
+    /// Input video file.
+    pub input: String,
+
+    /// Start time (hh:mm:ss). Defaults to the beginning of the file.
+    #[arg(long)]
+    pub start: Option<String>,
+
+    /// End time (hh:mm:ss). Defaults to the end of the file.
+    #[arg(long)]
+    pub end: Option<String>,
+
+    /// Output frame rate.
+    #[arg(long)]
+    pub fps: Option<f32>,
+
+    /// Output width: a preset name (nHD, HD, FullHD, WQHD, UHD) or a raw
+    /// pixel count.
+    #[arg(long)]
+    pub width: Option<WidthSpec>,
+
+    /// First frame to keep, 1-indexed.
+    #[arg(long)]
+    pub start_frame: Option<u32>,
+
+    /// Last frame to keep, 1-indexed.
+    #[arg(long)]
+    pub end_frame: Option<u32>,
+
+    /// Output file path. Defaults to `{input filename}.{format extension}`.
+    #[arg(long)]
+    pub output: Option<String>,
+
+    /// Dithering algorithm for the palette pass: bayer, floyd_steinberg,
+    /// sierra2, sierra2_4a, or none. Defaults to bayer.
+    #[arg(long)]
+    pub dither: Option<Dither>,
+
+    /// Bayer matrix scale (1-5), only used with `--dither bayer`.
+    /// Defaults to 3.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=5))]
+    pub bayer_scale: Option<u8>,
+
+    /// palettegen stats_mode: full, diff, or single. Defaults to diff.
+    #[arg(long)]
+    pub stats_mode: Option<StatsMode>,
+
+    /// Maximum palette size (2-256). Defaults to 256.
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=256))]
+    pub max_colors: Option<u16>,
+
+    /// Generate a fresh palette per frame instead of reusing one palette
+    /// for the whole clip. Defaults to false.
+    #[arg(long)]
+    pub new_palette: Option<bool>,
+
+    /// Output format: gif, webp, or apng. Defaults to gif. The output
+    /// extension follows the chosen format unless `--output` is set.
+    #[arg(long)]
+    pub format: Option<OutputFormat>,
+
+    /// libwebp_anim quality (0-100), only used with `--format webp`.
+    /// Defaults to 75.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    pub quality: Option<u8>,
+
+    /// Encode losslessly, only used with `--format webp`. Defaults to
+    /// false.
+    #[arg(long)]
+    pub lossless: Option<bool>,
+
+    /// libwebp_anim compression effort (0-6), only used with
+    /// `--format webp`. Defaults to 4.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=6))]
+    pub compression_level: Option<u8>,
+
+    /// Virtual-memory ceiling for every ffmpeg/ffprobe invocation, e.g.
+    /// `2G`, `512M`. Defaults to 2G.
+    #[arg(long)]
+    pub mem_limit: Option<MemLimit>,
+}
+
+impl Args {
+    /// Whether every flag needed to build a `GifJob` is present, so the
+    /// conversion can run without prompting.
+    pub fn is_complete(&self) -> bool {
+        self.start.is_some()
+            && self.end.is_some()
+            && self.fps.is_some()
+            && self.width.is_some()
+            && self.start_frame.is_some()
+            && self.end_frame.is_some()
+            && self.output.is_some()
+    }
+}
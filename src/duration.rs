@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Duration {
+    pub h: u32,
+    pub m: u32,
+    pub s: u32,
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.h, self.m, self.s)
+    }
+}
+
+impl Duration {
+    pub fn from_str(input: &str) -> Result<Self> {
+        let parts: Vec<&str> = input.split(':').collect();
+        let duration = match parts.len() {
+            1 => Self {
+                h: 0,
+                m: 0,
+                s: parts[0].parse().context("Failed to parse seconds")?,
+            },
+            2 => Self {
+                h: 0,
+                m: parts[0].parse().context("Failed to parse minutes")?,
+                s: parts[1].parse().context("Failed to parse seconds")?,
+            },
+            3 => Self {
+                h: parts[0].parse().context("Failed to parse hours")?,
+                m: parts[1].parse().context("Failed to parse minutes")?,
+                s: parts[2].parse().context("Failed to parse seconds")?,
+            },
+            _ => anyhow::bail!("Invalid duration format"),
+        };
+        Ok(duration)
+    }
+
+    pub fn from_seconds(seconds: f64) -> Self {
+        let total_seconds = seconds.floor() as u32;
+        Self {
+            h: total_seconds / 3600,
+            m: (total_seconds % 3600) / 60,
+            s: total_seconds % 60,
+        }
+    }
+
+    pub fn to_seconds(&self) -> u32 {
+        self.h * 3600 + self.m * 60 + self.s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_seconds_sums_all_three_components() {
+        assert_eq!(Duration { h: 0, m: 5, s: 0 }.to_seconds(), 300);
+        assert_eq!(Duration { h: 1, m: 0, s: 0 }.to_seconds(), 3600);
+        assert_eq!(Duration { h: 1, m: 5, s: 3 }.to_seconds(), 3903);
+    }
+}
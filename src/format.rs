@@ -0,0 +1,58 @@
+use anyhow::{bail, Result};
+use std::{fmt, str::FromStr};
+
+/// Output container/codec for the rendered animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Gif,
+    WebP,
+    Apng,
+}
+
+impl OutputFormat {
+    /// File extension (without the leading dot) for this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::WebP => "webp",
+            Self::Apng => "apng",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "gif" => Ok(Self::Gif),
+            "webp" => Ok(Self::WebP),
+            "apng" => Ok(Self::Apng),
+            _ => bail!("Unknown output format: {}", s),
+        }
+    }
+}
+
+/// Tunables for the single-pass `libwebp_anim` encode.
+#[derive(Debug, Clone, Copy)]
+pub struct WebpOpts {
+    pub quality: u8,
+    pub lossless: bool,
+    pub compression_level: u8,
+}
+
+impl Default for WebpOpts {
+    fn default() -> Self {
+        Self {
+            quality: 75,
+            lossless: false,
+            compression_level: 4,
+        }
+    }
+}
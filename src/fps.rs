@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// An exact rational frame rate (numerator/denominator), carried through
+/// the pipeline instead of a rounded float so the `fps=` filter matches
+/// the source exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fps {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl Fps {
+    pub fn new(num: u32, den: u32) -> Self {
+        let divisor = gcd(num, den).max(1);
+        Self {
+            num: num / divisor,
+            den: den / divisor,
+        }
+    }
+
+    /// Parse a `"num/den"` string as emitted by ffprobe's
+    /// `avg_frame_rate`/`r_frame_rate` fields. Returns `None` for the
+    /// `"0/0"` ffprobe uses when it can't determine a rate.
+    pub fn from_ffprobe(rate: &str) -> Option<Self> {
+        let (num, den) = rate.split_once('/')?;
+        let num: u32 = num.parse().ok()?;
+        let den: u32 = den.parse().ok()?;
+        if num == 0 || den == 0 {
+            return None;
+        }
+        Some(Self::new(num, den))
+    }
+
+    /// Approximate a decimal fps (e.g. from `--fps 23.976`) as a
+    /// rational, picking the common broadcast denominator (1001) when
+    /// the value looks like an NTSC-style rate.
+    pub fn from_decimal(value: f32) -> Self {
+        let ntsc_num = (value * 1.001).round() as u32 * 1000;
+        if value.fract() != 0.0 && (ntsc_num as f32 / 1001.0 - value).abs() < 0.01 {
+            return Self::new(ntsc_num, 1001);
+        }
+        Self::new((value * 1000.0).round() as u32, 1000)
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Number of frames covered by `seconds` at this rate, used to
+    /// derive `framecount` without the drift of a rounded float.
+    pub fn frames_for(&self, seconds: u32) -> u32 {
+        (seconds as u64 * self.num as u64 / self.den as u64) as u32
+    }
+}
+
+impl fmt::Display for Fps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_for_uses_exact_rational_math() {
+        assert_eq!(Fps::new(30, 1).frames_for(300), 9000);
+        assert_eq!(Fps::new(24000, 1001).frames_for(10), 239);
+    }
+}
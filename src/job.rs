@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::duration::Duration;
+use crate::format::{OutputFormat, WebpOpts};
+use crate::fps::Fps;
+use crate::mem_limit::MemLimit;
+use crate::palette::PaletteOpts;
+use crate::progress::run_with_progress;
+
+/// Scratch directory used to hold interactive frame-preview thumbnails.
+pub const PREVIEW_DIR: &str = "output_frames";
+
+/// Render a low-res JPEG per frame in `[start, end]` so a human can
+/// eyeball frame numbers before picking `start_frame`/`end_frame`. This
+/// is only useful for the interactive prompt flow; a headless `GifJob`
+/// already knows its frame range and has no use for it.
+pub fn generate_frame_previews(
+    input: &str,
+    start: Duration,
+    end: Duration,
+    fps: Fps,
+    mem_limit: MemLimit,
+) -> Result<()> {
+    fs::create_dir(PREVIEW_DIR).context("Failed to create preview frame directory")?;
+
+    let output = mem_limit
+        .wrap("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-nostats",
+            "-v",
+            "warning",
+            "-ss",
+            &start.to_string(),
+            "-to",
+            &end.to_string(),
+            "-i",
+            input,
+            "-fps_mode",
+            "vfr",
+            "-lavfi",
+            &format!(r"fps={},scale=600:-1:flags=lanczos", fps),
+            "-q:v",
+            "15",
+            "-y",
+            &format!("./{}/%04d.jpg", PREVIEW_DIR),
+        ])
+        .output()?;
+    check_command_success(&output)
+}
+
+/// A fully-specified GIF/WebP/APNG conversion, ready to run with no
+/// further input.
+#[derive(Debug)]
+pub struct GifJob {
+    pub input: String,
+    pub start: Duration,
+    pub end: Duration,
+    pub fps: Fps,
+    pub width: u32,
+    pub start_frame: u32,
+    pub end_frame: u32,
+    pub output: String,
+    pub format: OutputFormat,
+    pub palette_opts: PaletteOpts,
+    pub webp_opts: WebpOpts,
+    pub mem_limit: MemLimit,
+}
+
+impl GifJob {
+    /// Run the format-specific encode pipeline.
+    pub fn run(&self) -> Result<()> {
+        match self.format {
+            OutputFormat::Gif => self.run_gif(),
+            OutputFormat::WebP => self.run_webp(),
+            OutputFormat::Apng => self.run_apng(),
+        }?;
+
+        println!("{} 파일 생성 완료!", self.output);
+
+        Ok(())
+    }
+
+    /// Number of frames the encode passes will produce, used to size the
+    /// progress bar.
+    fn total_frames(&self) -> u32 {
+        self.end_frame.saturating_sub(self.start_frame) + 1
+    }
+
+    /// Two-pass palette extraction + GIF encode.
+    fn run_gif(&self) -> Result<()> {
+        let mut palette_cmd = self.mem_limit.wrap("ffmpeg");
+        palette_cmd.args([
+            "-hide_banner",
+            "-v",
+            "warning",
+            "-ss",
+            &self.start.to_string(),
+            "-to",
+            &self.end.to_string(),
+            "-i",
+            &self.input,
+            "-fps_mode",
+            "vfr",
+            "-lavfi",
+            &format!(
+                "fps={},trim=start_frame={}:end_frame={},setpts=PTS-STARTPTS,scale={}:-1:flags=lanczos,{}",
+                self.fps,
+                self.start_frame,
+                self.end_frame,
+                self.width,
+                self.palette_opts.palettegen_filter()
+            ),
+            "-y",
+            "palette.png",
+        ]);
+        check_command_success(&run_with_progress(&mut palette_cmd, self.total_frames())?)?;
+
+        let mut paletteuse_cmd = self.mem_limit.wrap("ffmpeg");
+        paletteuse_cmd.args([
+            "-hide_banner",
+            "-v",
+            "warning",
+            "-ss",
+            &self.start.to_string(),
+            "-to",
+            &self.end.to_string(),
+            "-i",
+            &self.input,
+            "-i",
+            "palette.png",
+            "-fps_mode",
+            "vfr",
+            "-lavfi",
+            &format!(
+                "fps={},trim=start_frame={}:end_frame={},setpts=PTS-STARTPTS,scale={}:-1:flags=lanczos[x];[x][1:v]{}",
+                self.fps,
+                self.start_frame,
+                self.end_frame,
+                self.width,
+                self.palette_opts.paletteuse_filter()
+            ),
+            "-y",
+            &self.output,
+        ]);
+        check_command_success(&run_with_progress(&mut paletteuse_cmd, self.total_frames())?)?;
+
+        fs::remove_file("palette.png")?; // Clean up palette file
+
+        Ok(())
+    }
+
+    /// Single-pass animated WebP encode.
+    fn run_webp(&self) -> Result<()> {
+        let mut cmd = self.mem_limit.wrap("ffmpeg");
+        cmd.args([
+            "-hide_banner",
+            "-v",
+            "warning",
+            "-ss",
+            &self.start.to_string(),
+            "-to",
+            &self.end.to_string(),
+            "-i",
+            &self.input,
+            "-fps_mode",
+            "vfr",
+            "-lavfi",
+            &format!(
+                "fps={},trim=start_frame={}:end_frame={},setpts=PTS-STARTPTS,scale={}:-1:flags=lanczos",
+                self.fps, self.start_frame, self.end_frame, self.width
+            ),
+            "-c:v",
+            "libwebp_anim",
+            "-quality",
+            &self.webp_opts.quality.to_string(),
+            "-lossless",
+            if self.webp_opts.lossless { "1" } else { "0" },
+            "-compression_level",
+            &self.webp_opts.compression_level.to_string(),
+            "-loop",
+            "0",
+            "-y",
+            &self.output,
+        ]);
+        check_command_success(&run_with_progress(&mut cmd, self.total_frames())?)
+    }
+
+    /// Single-pass animated PNG encode.
+    fn run_apng(&self) -> Result<()> {
+        let mut cmd = self.mem_limit.wrap("ffmpeg");
+        cmd.args([
+            "-hide_banner",
+            "-v",
+            "warning",
+            "-ss",
+            &self.start.to_string(),
+            "-to",
+            &self.end.to_string(),
+            "-i",
+            &self.input,
+            "-fps_mode",
+            "vfr",
+            "-lavfi",
+            &format!(
+                "fps={},trim=start_frame={}:end_frame={},setpts=PTS-STARTPTS,scale={}:-1:flags=lanczos",
+                self.fps, self.start_frame, self.end_frame, self.width
+            ),
+            "-f",
+            "apng",
+            "-plays",
+            "0",
+            "-y",
+            &self.output,
+        ]);
+        check_command_success(&run_with_progress(&mut cmd, self.total_frames())?)
+    }
+}
+
+fn check_command_success(output: &std::process::Output) -> Result<()> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Command failed: {}", stderr);
+    }
+    Ok(())
+}
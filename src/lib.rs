@@ -0,0 +1,20 @@
+pub mod cli;
+pub mod duration;
+pub mod format;
+pub mod fps;
+pub mod job;
+pub mod mem_limit;
+pub mod palette;
+pub mod probe;
+pub mod progress;
+pub mod resolution;
+
+pub use cli::Args;
+pub use duration::Duration;
+pub use format::{OutputFormat, WebpOpts};
+pub use fps::Fps;
+pub use job::{generate_frame_previews, GifJob, PREVIEW_DIR};
+pub use mem_limit::MemLimit;
+pub use palette::{Dither, PaletteOpts, StatsMode};
+pub use probe::{get_file_info, Resolution};
+pub use resolution::{ResolutionPreset, WidthSpec};
@@ -1,288 +1,332 @@
-use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
-use std::{
-    env, fmt, fs,
-    io::{self, stdin, Write},
-    process::Command,
+use anyhow::{ensure, Context, Result};
+use clap::Parser;
+use std::fs;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use gif_rs::{
+    cli::Args, generate_frame_previews, get_file_info, Dither, Duration, Fps, GifJob, MemLimit,
+    OutputFormat, PaletteOpts, Resolution, StatsMode, WebpOpts, WidthSpec, PREVIEW_DIR,
 };
 
-#[derive(Debug)]
-struct Duration {
-    h: u32,
-    m: u32,
-    s: u32,
-}
-
-impl fmt::Display for Duration {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}:{}", self.h, self.m, self.s)
-    }
-}
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let mem_limit = args.mem_limit.unwrap_or_default();
 
-impl Duration {
-    fn from_str(input: &str) -> Result<Self> {
-        let parts: Vec<&str> = input.split(':').collect();
-        let duration = match parts.len() {
-            1 => Self {
-                h: 0,
-                m: 0,
-                s: parts[0].parse().context("Failed to parse seconds")?,
-            },
-            2 => Self {
-                h: 0,
-                m: parts[0].parse().context("Failed to parse minutes")?,
-                s: parts[1].parse().context("Failed to parse seconds")?,
-            },
-            3 => Self {
-                h: parts[0].parse().context("Failed to parse hours")?,
-                m: parts[1].parse().context("Failed to parse minutes")?,
-                s: parts[2].parse().context("Failed to parse seconds")?,
-            },
-            _ => anyhow::bail!("Invalid duration format"),
-        };
-        Ok(duration)
-    }
+    let (resolution, duration, fps) = get_file_info(&args.input, mem_limit)?;
 
-    fn from_seconds(seconds: f64) -> Self {
-        let total_seconds = seconds.floor() as u32;
-        Self {
-            h: total_seconds / 3600,
-            m: (total_seconds % 3600) / 60,
-            s: total_seconds % 60,
+    println!("Input file: {}", args.input);
+    println!("Resolution: {}x{}", resolution.width, resolution.height);
+    println!("Duration: {}", duration);
+    println!("FPS: {} ({:.3})", fps, fps.as_f64());
+
+    let job = if args.is_complete() {
+        GifJob {
+            input: args.input.clone(),
+            start: Duration::from_str(args.start.as_ref().unwrap())?,
+            end: Duration::from_str(args.end.as_ref().unwrap())?,
+            fps: Fps::from_decimal(args.fps.unwrap()),
+            width: args.width.unwrap().resolve(&resolution),
+            start_frame: args.start_frame.unwrap(),
+            end_frame: args.end_frame.unwrap(),
+            output: args.output.clone().unwrap(),
+            format: args.format.unwrap_or(OutputFormat::Gif),
+            palette_opts: build_palette_opts(&args, false)?,
+            webp_opts: build_webp_opts(&args, false)?,
+            mem_limit,
         }
-    }
+    } else {
+        prompt_for_job(&args, resolution, duration, fps, mem_limit)?
+    };
 
-    fn to_seconds(&self) -> u32 {
-        self.h * 3600 * self.m * 60 + self.s
-    }
+    job.run()?;
+
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let stdin = stdin();
+/// Ask for whatever the user didn't already pass as a flag.
+fn prompt_for_job(
+    args: &Args,
+    resolution: Resolution,
+    duration: Duration,
+    fps: Fps,
+    mem_limit: MemLimit,
+) -> Result<GifJob> {
+    let start = match &args.start {
+        Some(s) => Duration::from_str(s)?,
+        None => {
+            let input = prompt_user("시작 시간 (hh:mm:ss, Enter=00:00:00): ");
+            if input.is_empty() {
+                Duration::from_seconds(0.0)
+            } else {
+                Duration::from_str(&input)?
+            }
+        }
+    };
+
+    let end = match &args.end {
+        Some(s) => Duration::from_str(s)?,
+        None => {
+            let input = prompt_user("끝 시간 (hh:mm:ss, Enter=끝까지): ");
+            if input.is_empty() {
+                duration
+            } else {
+                Duration::from_str(&input)?
+            }
+        }
+    };
 
-    let args: Vec<String> = env::args().collect();
+    let fps = match args.fps {
+        Some(fps) => Fps::from_decimal(fps),
+        None => {
+            let input = prompt_user(&format!("FPS (Enter={:.3}): ", fps.as_f64()));
+            if input.is_empty() {
+                fps
+            } else {
+                Fps::from_decimal(input.parse().context("Invalid FPS")?)
+            }
+        }
+    };
 
-    if args.len() != 2 {
-        println!("Usage: <program> <input_file>");
-        return Ok(());
+    // Only the interactive frame-picking flow below needs thumbnails to
+    // look at; skip the extra decode/encode pass when both frame bounds
+    // are already known.
+    let needs_frame_preview = args.start_frame.is_none() || args.end_frame.is_none();
+    if needs_frame_preview {
+        generate_frame_previews(&args.input, start, end, fps, mem_limit)?;
     }
 
-    let input_file = &args[1];
-    let filename = input_file.split('.').next().unwrap_or("output");
+    let width = match args.width {
+        Some(width) => width.resolve(&resolution),
+        None => {
+            let input = prompt_user("가로 픽셀 크기 (프리셋 또는 숫자, Enter=원본크기): ");
+            if input.is_empty() {
+                resolution.width
+            } else {
+                WidthSpec::from_str(&input)?.resolve(&resolution)
+            }
+        }
+    };
 
-    let (resolution, duration, fps) = get_file_info(input_file)?;
+    let framecount = fps.frames_for(end.to_seconds().saturating_sub(start.to_seconds()));
+
+    let start_frame = match args.start_frame {
+        Some(frame) => frame,
+        None => {
+            let input = prompt_user("시작 프레임 수: ");
+            if input.is_empty() {
+                1
+            } else {
+                input.parse().context("Start frame is not a number")?
+            }
+        }
+    };
 
-    println!("Input file: {}", input_file);
-    println!("Resolution: {}x{}", resolution.width, resolution.height);
-    println!("Duration: {}", duration);
-    println!("FPS: {}", fps);
+    let end_frame = match args.end_frame {
+        Some(frame) => frame,
+        None => {
+            let input = prompt_user(&format!("끝 프레임 수 (Enter={}): ", framecount));
+            if input.is_empty() {
+                framecount
+            } else {
+                input.parse().context("End frame is not a number")?
+            }
+        }
+    };
 
-    let start_time = prompt_user("시작 시간 (hh:mm:ss, Enter=00:00:00): ");
-    let start_time = if start_time.is_empty() {
-        Duration::from_seconds(0.0)
-    } else {
-        Duration::from_str(&start_time)?
+    if needs_frame_preview {
+        fs::remove_dir_all(PREVIEW_DIR).context("Failed to remove preview frame directory")?;
+    }
+
+    let format = match args.format {
+        Some(format) => format,
+        None => {
+            let input = prompt_user("출력 형식 (gif/webp/apng, Enter=gif): ");
+            if input.is_empty() {
+                OutputFormat::Gif
+            } else {
+                OutputFormat::from_str(&input)?
+            }
+        }
     };
 
-    let end_time = prompt_user("끝 시간 (hh:mm:ss, Enter=끝까지): ");
-    let end_time = if end_time.is_empty() {
-        duration
-    } else {
-        Duration::from_str(&end_time)?
+    let output = match &args.output {
+        Some(output) => output.clone(),
+        None => {
+            let filename = args.input.split('.').next().unwrap_or("output");
+            format!("{}.{}", filename, format.extension())
+        }
     };
 
-    println!("FPS (Enter={}): ", fps);
-    let mut fps_input = String::new();
-    stdin.read_line(&mut fps_input)?;
-
-    let framecount = (end_time.to_seconds() as f32 - start_time.to_seconds() as f32) * fps;
-
-    println!("가로 픽셀 크기 (Enter=원본크기): ");
-    let mut width_input = String::new();
-    stdin.read_line(&mut width_input)?;
-
-    let output_file = format!("{}.gif", filename);
-
-    let temp_folder = "output_frames";
-
-    let _ = fs::create_dir(temp_folder);
-
-    let _output_thumbnails = Command::new("ffmpeg")
-        .args([
-            "-hide_banner",
-            "-nostats",
-            "-v",
-            "warning",
-            "-ss",
-            &start_time.to_string(),
-            "-to",
-            &end_time.to_string(),
-            "-i",
-            input_file,
-            "-fps_mode",
-            "vfr",
-            "-lavfi",
-            &format!(r"fps={},scale=600:-1:flags=lanczos", fps),
-            "-q:v",
-            "15",
-            "-y",
-            &format!("./{}/%04d.jpg", temp_folder),
-        ])
-        .output()?;
-    check_command_success(&_output_thumbnails)?;
-
-    println!("시작 프레임 수");
-    let mut start_frame_input = String::new();
-    stdin.read_line(&mut start_frame_input)?;
-    let start_frame = start_frame_input.trim();
-    let start_frame = if start_frame.is_empty() {
-        1
-    } else {
-        start_frame.parse().expect("Start frame is not number")
+    let palette_opts = build_palette_opts(args, true)?;
+    let webp_opts = build_webp_opts(args, format == OutputFormat::WebP)?;
+
+    Ok(GifJob {
+        input: args.input.clone(),
+        start,
+        end,
+        fps,
+        width,
+        start_frame,
+        end_frame,
+        output,
+        format,
+        palette_opts,
+        webp_opts,
+        mem_limit,
+    })
+}
+
+/// Resolve palette tuning from flags, prompting for whatever is missing
+/// when `interactive` is set.
+fn build_palette_opts(args: &Args, interactive: bool) -> Result<PaletteOpts> {
+    let defaults = PaletteOpts::default();
+
+    let dither = match args.dither {
+        Some(dither) => dither,
+        None if interactive => {
+            let input = prompt_user(&format!(
+                "디더링 알고리즘 (bayer/floyd_steinberg/sierra2/sierra2_4a/none, Enter={}): ",
+                defaults.dither
+            ));
+            if input.is_empty() {
+                defaults.dither
+            } else {
+                Dither::from_str(&input)?
+            }
+        }
+        None => defaults.dither,
     };
 
-    println!("끝 프레임 수 (Enter={})", framecount);
-    let mut end_frame_input = String::new();
-    stdin.read_line(&mut end_frame_input)?;
-    let end_frame = end_frame_input.trim();
-    let end_frame = if end_frame.is_empty() {
-        framecount
-    } else {
-        end_frame.parse().expect("Start frame is not number")
+    let bayer_scale = match args.bayer_scale {
+        Some(scale) => scale,
+        None if interactive && dither == Dither::Bayer => {
+            let input = prompt_user(&format!(
+                "Bayer scale (1-5, Enter={}): ",
+                defaults.bayer_scale
+            ));
+            if input.is_empty() {
+                defaults.bayer_scale
+            } else {
+                let scale: u8 = input.parse().context("Invalid bayer scale")?;
+                ensure!((1..=5).contains(&scale), "Bayer scale must be 1-5");
+                scale
+            }
+        }
+        None => defaults.bayer_scale,
     };
 
-    let output_palette = Command::new("ffmpeg")
-        .args([
-            "-hide_banner",
-            "-nostats",
-            "-v",
-            "warning",
-            "-ss",
-            &start_time.to_string(),
-            "-to",
-            &end_time.to_string(),
-            "-i",
-            input_file,
-            "-fps_mode",
-            "vfr",
-            "-lavfi",
-            &format!(
-                "fps={},trim=start_frame={}:end_frame={},setpts=PTS-STARTPTS,scale={}:-1:flags=lanczos,palettegen=stats_mode=diff",
-                fps, start_frame, end_frame, resolution.width
-            ),
-            "-y",
-            "palette.png",
-        ])
-        .output()?;
-    check_command_success(&output_palette)?;
-
-    let output = Command::new("ffmpeg")
-        .args([
-            "-hide_banner",
-            "-nostats",
-            "-v",
-            "warning",
-            "-ss",
-            &start_time.to_string(),
-            "-to",
-            &end_time.to_string(),
-            "-i",
-            input_file,
-            "-i",
-            "palette.png",
-            "-fps_mode",
-            "vfr",
-            "-lavfi",
-            &format!(
-                "fps={},trim=start_frame={}:end_frame={},setpts=PTS-STARTPTS,scale={}:-1:flags=lanczos[x];[x][1:v]paletteuse=dither=bayer:bayer_scale=3",
-                fps, start_frame, end_frame, resolution.width
-            ),
-            "-y",
-            &output_file,
-        ])
-        .output()?;
-    check_command_success(&output)?;
-
-    fs::remove_file("palette.png")?; // Clean up palette file
-    fs::remove_dir_all(temp_folder)?;
-
-    println!("{} 파일 생성 완료!", output_file);
+    let stats_mode = match args.stats_mode {
+        Some(stats_mode) => stats_mode,
+        None if interactive => {
+            let input = prompt_user(&format!(
+                "palettegen stats_mode (full/diff/single, Enter={}): ",
+                defaults.stats_mode
+            ));
+            if input.is_empty() {
+                defaults.stats_mode
+            } else {
+                StatsMode::from_str(&input)?
+            }
+        }
+        None => defaults.stats_mode,
+    };
 
-    Ok(())
-}
+    let max_colors = match args.max_colors {
+        Some(max_colors) => max_colors,
+        None if interactive => {
+            let input = prompt_user(&format!(
+                "팔레트 최대 색상 수 (2-256, Enter={}): ",
+                defaults.max_colors
+            ));
+            if input.is_empty() {
+                defaults.max_colors
+            } else {
+                let max_colors: u16 = input.parse().context("Invalid max_colors")?;
+                ensure!((2..=256).contains(&max_colors), "max_colors must be 2-256");
+                max_colors
+            }
+        }
+        None => defaults.max_colors,
+    };
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ProbeInfo {
-    streams: Vec<StreamInfo>,
-    format: FormatInfo,
-}
+    let new_palette = match args.new_palette {
+        Some(new_palette) => new_palette,
+        None if interactive => {
+            let input = prompt_user(&format!(
+                "프레임마다 새 팔레트 사용? (y/N, Enter={}): ",
+                if defaults.new_palette { "y" } else { "N" }
+            ));
+            matches!(input.to_lowercase().as_str(), "y" | "yes")
+        }
+        None => defaults.new_palette,
+    };
 
-#[derive(Debug, Serialize, Deserialize)]
-struct StreamInfo {
-    width: Option<u32>,
-    height: Option<u32>,
-    codec_type: String,
-    r_frame_rate: String,
+    Ok(PaletteOpts {
+        dither,
+        bayer_scale,
+        stats_mode,
+        max_colors,
+        new_palette,
+    })
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct FormatInfo {
-    duration: String,
-}
+/// Resolve WebP tuning from flags, prompting for whatever is missing
+/// when `interactive` is set. Only consulted when the output format is
+/// webp.
+fn build_webp_opts(args: &Args, interactive: bool) -> Result<WebpOpts> {
+    let defaults = WebpOpts::default();
+
+    let quality = match args.quality {
+        Some(quality) => quality,
+        None if interactive => {
+            let input = prompt_user(&format!("WebP 품질 (0-100, Enter={}): ", defaults.quality));
+            if input.is_empty() {
+                defaults.quality
+            } else {
+                let quality: u8 = input.parse().context("Invalid quality")?;
+                ensure!((0..=100).contains(&quality), "Quality must be 0-100");
+                quality
+            }
+        }
+        None => defaults.quality,
+    };
 
-#[derive(Debug)]
-struct Resolution {
-    width: u32,
-    height: u32,
-}
+    let lossless = match args.lossless {
+        Some(lossless) => lossless,
+        None if interactive => {
+            let input = prompt_user(&format!(
+                "무손실 인코딩? (y/N, Enter={}): ",
+                if defaults.lossless { "y" } else { "N" }
+            ));
+            matches!(input.to_lowercase().as_str(), "y" | "yes")
+        }
+        None => defaults.lossless,
+    };
 
-fn get_file_info(filename: &str) -> Result<(Resolution, Duration, f32)> {
-    let output = Command::new("ffprobe")
-        .args([
-            "-v",
-            "quiet",
-            "-print_format",
-            "json",
-            "-show_format",
-            "-show_streams",
-            filename,
-        ])
-        .output()
-        .context("Failed to execute ffprobe")?;
-
-    let info: ProbeInfo =
-        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
-    let stream = info
-        .streams
-        .iter()
-        .find(|s| s.codec_type == "video")
-        .context("No video stream found")?;
-
-    let width = stream.width.context("Width not found")?;
-    let height = stream.height.context("Height not found")?;
-    let resolution = Resolution { width, height };
-
-    let duration = Duration::from_seconds(
-        info.format
-            .duration
-            .parse::<f64>()
-            .context("Invalid duration")?,
-    );
-
-    let fps_parts: Vec<&str> = stream.r_frame_rate.split('/').collect();
-    let fps = if fps_parts.len() == 2 {
-        let numerator = fps_parts[0]
-            .parse::<f32>()
-            .context("Invalid FPS numerator")?;
-        let denominator = fps_parts[1]
-            .parse::<f32>()
-            .context("Invalid FPS denominator")?;
-        numerator / denominator
-    } else {
-        anyhow::bail!("Invalid FPS format")
+    let compression_level = match args.compression_level {
+        Some(level) => level,
+        None if interactive => {
+            let input = prompt_user(&format!(
+                "압축 강도 (0-6, Enter={}): ",
+                defaults.compression_level
+            ));
+            if input.is_empty() {
+                defaults.compression_level
+            } else {
+                let level: u8 = input.parse().context("Invalid compression level")?;
+                ensure!((0..=6).contains(&level), "Compression level must be 0-6");
+                level
+            }
+        }
+        None => defaults.compression_level,
     };
 
-    Ok((resolution, duration, fps))
+    Ok(WebpOpts {
+        quality,
+        lossless,
+        compression_level,
+    })
 }
 
 fn prompt_user(prompt: &str) -> String {
@@ -292,11 +336,3 @@ fn prompt_user(prompt: &str) -> String {
     io::stdin().read_line(&mut input).unwrap();
     input.trim().to_string()
 }
-
-fn check_command_success(output: &std::process::Output) -> Result<()> {
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Command failed: {}", stderr);
-    }
-    Ok(())
-}
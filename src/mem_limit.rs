@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use std::{fmt, process::Command, str::FromStr};
+
+/// Virtual-memory ceiling applied to every ffmpeg/ffprobe invocation via
+/// a `ulimit -v` shell wrapper, so large sources can't trigger the OOM
+/// killer on memory-constrained machines.
+#[derive(Debug, Clone, Copy)]
+pub struct MemLimit {
+    bytes: u64,
+}
+
+impl MemLimit {
+    fn as_kib(&self) -> u64 {
+        self.bytes.div_ceil(1024)
+    }
+
+    /// Build a `Command` for `program` that enforces this limit via
+    /// `ulimit -v` before exec'ing into the real process. Further args
+    /// can be appended to the returned command as usual.
+    pub fn wrap(&self, program: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(format!("ulimit -v {} && exec \"$0\" \"$@\"", self.as_kib()))
+            .arg(program);
+        cmd
+    }
+}
+
+impl Default for MemLimit {
+    fn default() -> Self {
+        Self::from_str("2G").expect("2G is a valid mem-limit")
+    }
+}
+
+impl fmt::Display for MemLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bytes)
+    }
+}
+
+impl FromStr for MemLimit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (digits, multiplier) = match s.chars().last() {
+            Some(c) if c.eq_ignore_ascii_case(&'g') => (&s[..s.len() - 1], 1024 * 1024 * 1024),
+            Some(c) if c.eq_ignore_ascii_case(&'m') => (&s[..s.len() - 1], 1024 * 1024),
+            Some(c) if c.eq_ignore_ascii_case(&'k') => (&s[..s.len() - 1], 1024),
+            _ => (s, 1),
+        };
+        let value: u64 = digits.trim().parse().context("Invalid mem-limit")?;
+        Ok(Self {
+            bytes: value * multiplier,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gigabyte_suffix() {
+        assert_eq!(MemLimit::from_str("2G").unwrap().bytes, 2 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parses_megabyte_suffix() {
+        assert_eq!(MemLimit::from_str("512M").unwrap().bytes, 512 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parses_kilobyte_suffix() {
+        assert_eq!(MemLimit::from_str("1024k").unwrap().bytes, 1024 * 1024);
+    }
+
+    #[test]
+    fn parses_bare_number_as_bytes() {
+        assert_eq!(MemLimit::from_str("2048").unwrap().bytes, 2048);
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{bail, Result};
+use std::{fmt, str::FromStr};
+
+/// Dithering algorithm for the `paletteuse` pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dither {
+    Bayer,
+    FloydSteinberg,
+    Sierra2,
+    Sierra2_4a,
+    None,
+}
+
+impl fmt::Display for Dither {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Bayer => "bayer",
+            Self::FloydSteinberg => "floyd_steinberg",
+            Self::Sierra2 => "sierra2",
+            Self::Sierra2_4a => "sierra2_4a",
+            Self::None => "none",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Dither {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bayer" => Ok(Self::Bayer),
+            "floyd_steinberg" | "floyd_steffenberg" => Ok(Self::FloydSteinberg),
+            "sierra2" => Ok(Self::Sierra2),
+            "sierra2_4a" => Ok(Self::Sierra2_4a),
+            "none" => Ok(Self::None),
+            _ => bail!("Unknown dither algorithm: {}", s),
+        }
+    }
+}
+
+/// `palettegen` sampling mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsMode {
+    Full,
+    Diff,
+    Single,
+}
+
+impl fmt::Display for StatsMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Full => "full",
+            Self::Diff => "diff",
+            Self::Single => "single",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for StatsMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "full" => Ok(Self::Full),
+            "diff" => Ok(Self::Diff),
+            "single" => Ok(Self::Single),
+            _ => bail!("Unknown stats_mode: {}", s),
+        }
+    }
+}
+
+/// Tunables for the `palettegen`/`paletteuse` pair, shared between both
+/// passes so they stay consistent.
+#[derive(Debug, Clone, Copy)]
+pub struct PaletteOpts {
+    pub dither: Dither,
+    pub bayer_scale: u8,
+    pub stats_mode: StatsMode,
+    pub max_colors: u16,
+    pub new_palette: bool,
+}
+
+impl Default for PaletteOpts {
+    fn default() -> Self {
+        Self {
+            dither: Dither::Bayer,
+            bayer_scale: 3,
+            stats_mode: StatsMode::Diff,
+            max_colors: 256,
+            new_palette: false,
+        }
+    }
+}
+
+impl PaletteOpts {
+    /// The `palettegen=...` filter fragment.
+    pub fn palettegen_filter(&self) -> String {
+        format!(
+            "palettegen=stats_mode={}:max_colors={}",
+            self.stats_mode, self.max_colors
+        )
+    }
+
+    /// The `paletteuse=...` filter fragment.
+    pub fn paletteuse_filter(&self) -> String {
+        let mut filter = match self.dither {
+            Dither::Bayer => format!("paletteuse=dither=bayer:bayer_scale={}", self.bayer_scale),
+            other => format!("paletteuse=dither={}", other),
+        };
+        filter.push_str(&format!(":new={}", self.new_palette as u8));
+        filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettegen_filter_includes_stats_mode_and_max_colors() {
+        let opts = PaletteOpts {
+            stats_mode: StatsMode::Full,
+            max_colors: 64,
+            ..PaletteOpts::default()
+        };
+        assert_eq!(
+            opts.palettegen_filter(),
+            "palettegen=stats_mode=full:max_colors=64"
+        );
+    }
+
+    #[test]
+    fn paletteuse_filter_includes_bayer_scale_for_bayer_dither() {
+        let opts = PaletteOpts {
+            dither: Dither::Bayer,
+            bayer_scale: 4,
+            new_palette: true,
+            ..PaletteOpts::default()
+        };
+        assert_eq!(
+            opts.paletteuse_filter(),
+            "paletteuse=dither=bayer:bayer_scale=4:new=1"
+        );
+    }
+
+    #[test]
+    fn paletteuse_filter_omits_bayer_scale_for_other_dithers() {
+        let opts = PaletteOpts {
+            dither: Dither::FloydSteinberg,
+            new_palette: false,
+            ..PaletteOpts::default()
+        };
+        assert_eq!(
+            opts.paletteuse_filter(),
+            "paletteuse=dither=floyd_steinberg:new=0"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::duration::Duration;
+use crate::fps::Fps;
+use crate::mem_limit::MemLimit;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProbeInfo {
+    streams: Vec<StreamInfo>,
+    format: FormatInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StreamInfo {
+    width: Option<u32>,
+    height: Option<u32>,
+    codec_type: String,
+    r_frame_rate: String,
+    avg_frame_rate: String,
+    #[serde(default)]
+    disposition: Disposition,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Disposition {
+    #[serde(default)]
+    attached_pic: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FormatInfo {
+    duration: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+pub fn get_file_info(filename: &str, mem_limit: MemLimit) -> Result<(Resolution, Duration, Fps)> {
+    let output = mem_limit
+        .wrap("ffprobe")
+        .args([
+            "-v",
+            "quiet",
+            "-print_format",
+            "json",
+            "-show_format",
+            "-show_streams",
+            filename,
+        ])
+        .output()
+        .context("Failed to execute ffprobe")?;
+
+    let info: ProbeInfo =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    let stream = info
+        .streams
+        .iter()
+        .find(|s| s.codec_type == "video" && s.disposition.attached_pic == 0)
+        .context("No video stream found")?;
+
+    let width = stream.width.context("Width not found")?;
+    let height = stream.height.context("Height not found")?;
+    let resolution = Resolution { width, height };
+
+    let duration = Duration::from_seconds(
+        info.format
+            .duration
+            .parse::<f64>()
+            .context("Invalid duration")?,
+    );
+
+    let fps = Fps::from_ffprobe(&stream.avg_frame_rate)
+        .or_else(|| Fps::from_ffprobe(&stream.r_frame_rate))
+        .context("Invalid FPS format")?;
+
+    Ok((resolution, duration, fps))
+}
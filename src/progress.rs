@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{
+    io::{BufRead, BufReader, Read},
+    process::{Command, Output, Stdio},
+    thread,
+};
+
+/// Run an ffmpeg command while rendering a progress bar driven by its
+/// `-progress pipe:1` output, so multi-minute renders aren't a frozen
+/// terminal.
+///
+/// `cmd` should not already set `-progress`/`-nostats`; they're added
+/// here. `total_frames` sizes the bar. The exit status is still checked
+/// by the caller via [`Output::status`], same as a plain `.output()`.
+pub fn run_with_progress(cmd: &mut Command, total_frames: u32) -> Result<Output> {
+    cmd.args(["-progress", "pipe:1", "-nostats"]);
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn ffmpeg")?;
+
+    let stdout = child.stdout.take().expect("ffmpeg stdout was piped");
+    let mut stderr_pipe = child.stderr.take().expect("ffmpeg stderr was piped");
+
+    let stderr_handle = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr_pipe.read_to_end(&mut buf);
+        buf
+    });
+
+    let bar = ProgressBar::new(total_frames as u64);
+    bar.set_style(
+        ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} frames ({msg})")
+            .unwrap_or_else(|_| ProgressStyle::default_bar()),
+    );
+
+    for line in BufReader::new(stdout).lines() {
+        let line = line.context("Failed to read ffmpeg progress output")?;
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key {
+            "frame" => {
+                if let Ok(frame) = value.parse::<u64>() {
+                    bar.set_position(frame.min(total_frames as u64));
+                }
+            }
+            "out_time_us" => {
+                if let Ok(out_time_us) = value.parse::<i64>() {
+                    bar.set_message(format!("{:.1}s", out_time_us as f64 / 1_000_000.0));
+                }
+            }
+            "progress" if value == "end" => bar.set_position(total_frames as u64),
+            _ => {}
+        }
+    }
+
+    let status = child.wait().context("Failed to wait on ffmpeg")?;
+    let stderr = stderr_handle.join().unwrap_or_default();
+
+    bar.finish_and_clear();
+
+    Ok(Output {
+        status,
+        stdout: Vec::new(),
+        stderr,
+    })
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::{fmt, str::FromStr};
+
+use crate::probe::Resolution;
+
+/// Named output-width presets so users can target a familiar size
+/// instead of guessing raw pixel counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionPreset {
+    NHD,
+    HD,
+    FullHD,
+    WQHD,
+    UHD,
+}
+
+impl ResolutionPreset {
+    pub fn width(&self) -> u32 {
+        match self {
+            Self::NHD => 640,
+            Self::HD => 1280,
+            Self::FullHD => 1920,
+            Self::WQHD => 2560,
+            Self::UHD => 3840,
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        match self {
+            Self::NHD => 360,
+            Self::HD => 720,
+            Self::FullHD => 1080,
+            Self::WQHD => 1440,
+            Self::UHD => 2160,
+        }
+    }
+}
+
+impl fmt::Display for ResolutionPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::NHD => "nHD",
+            Self::HD => "HD",
+            Self::FullHD => "FullHD",
+            Self::WQHD => "WQHD",
+            Self::UHD => "UHD",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ResolutionPreset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "nhd" => Ok(Self::NHD),
+            "hd" => Ok(Self::HD),
+            "fullhd" | "full-hd" => Ok(Self::FullHD),
+            "wqhd" => Ok(Self::WQHD),
+            "uhd" => Ok(Self::UHD),
+            _ => anyhow::bail!("Unknown resolution preset: {}", s),
+        }
+    }
+}
+
+/// Either a named preset or a raw pixel width, as accepted at the
+/// `--width` flag / width prompt.
+#[derive(Debug, Clone, Copy)]
+pub enum WidthSpec {
+    Preset(ResolutionPreset),
+    Pixels(u32),
+}
+
+impl WidthSpec {
+    /// Resolve to a concrete pixel width, clamped so the GIF is never
+    /// upscaled beyond the probed source resolution.
+    pub fn resolve(&self, source: &Resolution) -> u32 {
+        let width = match self {
+            Self::Preset(preset) => preset.width(),
+            Self::Pixels(width) => *width,
+        };
+        width.min(source.width)
+    }
+}
+
+impl FromStr for WidthSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(preset) = ResolutionPreset::from_str(s) {
+            return Ok(Self::Preset(preset));
+        }
+        let width = s.parse::<u32>().context("Invalid width")?;
+        Ok(Self::Pixels(width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_clamps_to_source_width() {
+        let source = Resolution {
+            width: 800,
+            height: 600,
+        };
+        assert_eq!(WidthSpec::Pixels(1920).resolve(&source), 800);
+        assert_eq!(WidthSpec::Preset(ResolutionPreset::HD).resolve(&source), 800);
+    }
+
+    #[test]
+    fn resolve_keeps_width_under_source() {
+        let source = Resolution {
+            width: 1920,
+            height: 1080,
+        };
+        assert_eq!(WidthSpec::Pixels(640).resolve(&source), 640);
+        assert_eq!(
+            WidthSpec::Preset(ResolutionPreset::HD).resolve(&source),
+            1280
+        );
+    }
+}